@@ -1,39 +1,370 @@
 use eframe::egui;
-use serialport::{available_ports, SerialPort, SerialPortType};
+use egui_plot::{Line, Plot, PlotPoints};
+#[cfg(windows)]
+use local_encoding::{Encoder, Encoding};
+use rmodbus::{client::ModbusRequest, guess_response_frame_len, ModbusProto};
+use serialport::{available_ports, DataBits, FlowControl, Parity, SerialPort, SerialPortType, StopBits};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::Duration;
 use std::thread;
 
+const MAX_RAW_OUTPUT_BYTES: usize = 4096;
+
+/// Caps the leftover-partial-line buffer used to reassemble telemetry lines
+/// split across channel messages. Every received chunk passes through this
+/// buffer, whatever the protocol or view mode, so a stream that never emits
+/// a newline would otherwise grow it without bound.
+const MAX_TELEMETRY_LINE_BUFFER_BYTES: usize = 4096;
+
+#[derive(PartialEq)]
+enum ViewMode {
+    Text,
+    Plot,
+}
+
+/// How captured output bytes are rendered in the output area.
+#[derive(Clone, Copy, PartialEq)]
+enum TextEncoding {
+    Utf8Lossy,
+    LocalCodepage,
+    HexDump,
+}
+
+impl TextEncoding {
+    fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8Lossy => "UTF-8 (lossy)",
+            TextEncoding::LocalCodepage => "local codepage",
+            TextEncoding::HexDump => "hex + ASCII",
+        }
+    }
+}
+
+/// Commands sent from the UI thread to the background reader thread.
+enum PortCmd {
+    Disconnect,
+}
+
+/// Decodes bytes using the host's local/system codepage.
+///
+/// `local_encoding`'s `ANSI` codec shells out to the Windows codepage API,
+/// so it's only available there; other platforms fall back to Windows-1252
+/// via `encoding_rs`, which covers most non-UTF-8 Latin serial gear.
+#[cfg(windows)]
+fn decode_local_codepage(bytes: &[u8]) -> String {
+    Encoding::ANSI
+        .to_string(bytes)
+        .unwrap_or_else(|_| String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(not(windows))]
+fn decode_local_codepage(bytes: &[u8]) -> String {
+    let (decoded, _, _) = encoding_rs::WINDOWS_1252.decode(bytes);
+    decoded.into_owned()
+}
+
+/// Renders a classic offset / hex-bytes / ASCII-sidebar dump, 16 bytes per row.
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        out.push_str(&format!("{:08x}  ", row * 16));
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(b) => out.push_str(&format!("{:02x} ", b)),
+                None => out.push_str("   "),
+            }
+            if i == 7 {
+                out.push(' ');
+            }
+        }
+        out.push_str(" |");
+        for &b in chunk {
+            let c = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
+            out.push(c);
+        }
+        out.push_str("|\n");
+    }
+    out
+}
+
+/// Which wire protocol the connected port is speaking.
+#[derive(Clone, Copy, PartialEq)]
+enum ProtocolMode {
+    PlainText,
+    FramedPacket,
+    ModbusRtu,
+}
+
+/// The Modbus function codes exposed by the polling panel.
+#[derive(Clone, Copy, PartialEq)]
+enum ModbusFunction {
+    ReadHoldingRegisters,
+    ReadInputRegisters,
+    WriteSingleRegister,
+    WriteMultipleRegisters,
+}
+
+impl ModbusFunction {
+    fn label(self) -> &'static str {
+        match self {
+            ModbusFunction::ReadHoldingRegisters => "read holding registers",
+            ModbusFunction::ReadInputRegisters => "read input registers",
+            ModbusFunction::WriteSingleRegister => "write single register",
+            ModbusFunction::WriteMultipleRegisters => "write multiple registers",
+        }
+    }
+
+    fn is_write(self) -> bool {
+        matches!(
+            self,
+            ModbusFunction::WriteSingleRegister | ModbusFunction::WriteMultipleRegisters
+        )
+    }
+}
+
+/// The structured message types the framed-packet decoder can unpack a
+/// COBS frame into. Add a variant here and to [`decode_frame`] to teach
+/// the terminal a new wire format.
+#[derive(Clone, Copy, PartialEq)]
+enum MessageKind {
+    SensorReading,
+    StatusMessage,
+}
+
+impl MessageKind {
+    fn label(self) -> &'static str {
+        match self {
+            MessageKind::SensorReading => "sensor reading",
+            MessageKind::StatusMessage => "status message",
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SensorReading {
+    id: u8,
+    value: f32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct StatusMessage {
+    flags: u8,
+    uptime_ms: u32,
+}
+
+/// Largest COBS-stuffed frame the accumulator will hold before giving up on
+/// it. Bounds memory use against a stream with the wrong mode selected, or
+/// a device that never emits the `0x00` delimiter.
+const MAX_COBS_FRAME_LEN: usize = 512;
+
+/// Accumulates raw serial bytes and yields complete COBS frames.
+///
+/// COBS replaces every zero byte in a frame's payload with a pointer to
+/// the next zero and prefixes the frame with an overhead byte, so the
+/// `0x00` delimiter can never appear inside the encoded data. The
+/// accumulator just has to watch for that delimiter and hand back
+/// whatever stuffed bytes came before it.
+struct CobsAccumulator {
+    buffer: Vec<u8>,
+    overflowed: bool,
+}
+
+impl CobsAccumulator {
+    fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            overflowed: false,
+        }
+    }
+
+    fn feed(&mut self, bytes: &[u8]) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        for &byte in bytes {
+            if byte == 0x00 {
+                if !self.buffer.is_empty() {
+                    frames.push(std::mem::take(&mut self.buffer));
+                }
+            } else {
+                self.buffer.push(byte);
+                if self.buffer.len() > MAX_COBS_FRAME_LEN {
+                    self.buffer.clear();
+                    self.overflowed = true;
+                }
+            }
+        }
+        frames
+    }
+
+    /// Reports and clears whether a frame was dropped for exceeding
+    /// `MAX_COBS_FRAME_LEN` since the last call.
+    fn take_overflow(&mut self) -> bool {
+        std::mem::replace(&mut self.overflowed, false)
+    }
+}
+
+/// De-stuffs and deserializes a single COBS frame as `kind`.
+fn decode_frame(kind: MessageKind, frame: &mut [u8]) -> Option<String> {
+    match kind {
+        MessageKind::SensorReading => postcard::from_bytes_cobs::<SensorReading>(frame)
+            .ok()
+            .map(|msg| format!("{:?}", msg)),
+        MessageKind::StatusMessage => postcard::from_bytes_cobs::<StatusMessage>(frame)
+            .ok()
+            .map(|msg| format!("{:?}", msg)),
+    }
+}
+
+/// Parses a telemetry line into named fields.
+///
+/// `key=value` pairs use the key as the field name; plain comma- or
+/// whitespace-separated floats are named by their positional index.
+fn parse_telemetry_line(line: &str) -> Vec<(String, f64)> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Vec::new();
+    }
+
+    let tokens: Vec<&str> = if line.contains(',') {
+        line.split(',').map(|t| t.trim()).collect()
+    } else {
+        line.split_whitespace().collect()
+    };
+
+    let mut fields = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some((key, value)) = token.split_once('=') {
+            if let Ok(value) = value.trim().parse::<f64>() {
+                fields.push((key.trim().to_string(), value));
+            }
+        } else if let Ok(value) = token.parse::<f64>() {
+            fields.push((i.to_string(), value));
+        }
+    }
+    fields
+}
+
 struct ComPortApp {
     available_ports: Vec<String>,
     selected_port: Option<String>,
     baud_rates: Vec<u32>,
     selected_baud_rate: u32,
+    data_bits_options: Vec<DataBits>,
+    selected_data_bits: DataBits,
+    parity_options: Vec<Parity>,
+    selected_parity: Parity,
+    stop_bits_options: Vec<StopBits>,
+    selected_stop_bits: StopBits,
+    flow_control_options: Vec<FlowControl>,
+    selected_flow_control: FlowControl,
     input_buffer: String,
     output_buffer: String,
+    raw_output: Vec<u8>,
+    text_encoding: TextEncoding,
     port_handle: Option<Box<dyn SerialPort>>,
-    rx: Receiver<String>,
-    tx: Sender<String>,
+    rx: Receiver<Vec<u8>>,
+    tx: Sender<Vec<u8>>,
+    cmd_tx: Option<Sender<PortCmd>>,
+    reader_thread: Option<thread::JoinHandle<()>>,
+    view_mode: ViewMode,
+    plot_series: BTreeMap<String, Vec<[f64; 2]>>,
+    telemetry_line_buffer: String,
+    plot_sample_count: u64,
+    max_plot_points: usize,
+    plot_x_field: Option<String>,
+    plot_y_field: Option<String>,
+    protocol_mode: ProtocolMode,
+    selected_message_kind: MessageKind,
+    modbus_slave_id: u8,
+    modbus_function: ModbusFunction,
+    modbus_start_address: u16,
+    modbus_count: u16,
+    modbus_write_input: String,
+    modbus_timeout_ms: u64,
+    modbus_registers: Vec<(u16, u16)>,
+    modbus_error: Option<String>,
 }
 
 impl Default for ComPortApp {
     fn default() -> Self {
         let (tx, rx) = mpsc::channel();
-        
+
         Self {
             available_ports: Vec::new(),
             selected_port: None,
             baud_rates: vec![9600, 19200, 38400, 57600, 115200],
             selected_baud_rate: 9600,
+            data_bits_options: vec![DataBits::Five, DataBits::Six, DataBits::Seven, DataBits::Eight],
+            selected_data_bits: DataBits::Eight,
+            parity_options: vec![Parity::None, Parity::Odd, Parity::Even],
+            selected_parity: Parity::None,
+            stop_bits_options: vec![StopBits::One, StopBits::Two],
+            selected_stop_bits: StopBits::One,
+            flow_control_options: vec![FlowControl::None, FlowControl::Software, FlowControl::Hardware],
+            selected_flow_control: FlowControl::None,
             input_buffer: String::new(),
             output_buffer: String::new(),
+            raw_output: Vec::new(),
+            text_encoding: TextEncoding::Utf8Lossy,
             port_handle: None,
+            cmd_tx: None,
+            reader_thread: None,
             rx,
             tx,
+            view_mode: ViewMode::Text,
+            plot_series: BTreeMap::new(),
+            telemetry_line_buffer: String::new(),
+            plot_sample_count: 0,
+            max_plot_points: 500,
+            plot_x_field: None,
+            plot_y_field: None,
+            protocol_mode: ProtocolMode::PlainText,
+            selected_message_kind: MessageKind::SensorReading,
+            modbus_slave_id: 1,
+            modbus_function: ModbusFunction::ReadHoldingRegisters,
+            modbus_start_address: 0,
+            modbus_count: 1,
+            modbus_write_input: String::new(),
+            modbus_timeout_ms: 300,
+            modbus_registers: Vec::new(),
+            modbus_error: None,
         }
     }
 }
 
+fn data_bits_label(bits: DataBits) -> &'static str {
+    match bits {
+        DataBits::Five => "5",
+        DataBits::Six => "6",
+        DataBits::Seven => "7",
+        DataBits::Eight => "8",
+    }
+}
+
+fn parity_label(parity: Parity) -> &'static str {
+    match parity {
+        Parity::None => "none",
+        Parity::Odd => "odd",
+        Parity::Even => "even",
+    }
+}
+
+fn stop_bits_label(stop_bits: StopBits) -> &'static str {
+    match stop_bits {
+        StopBits::One => "1",
+        StopBits::Two => "2",
+    }
+}
+
+fn flow_control_label(flow_control: FlowControl) -> &'static str {
+    match flow_control {
+        FlowControl::None => "none",
+        FlowControl::Software => "software",
+        FlowControl::Hardware => "hardware",
+    }
+}
+
 impl ComPortApp {
     fn new(cc: &eframe::CreationContext<'_>) -> Self {
         let mut app = Self::default();
@@ -53,27 +384,65 @@ impl ComPortApp {
     fn connect_port(&mut self) {
         if let Some(port_name) = &self.selected_port {
             match serialport::new(port_name, self.selected_baud_rate)
+                .data_bits(self.selected_data_bits)
+                .parity(self.selected_parity)
+                .stop_bits(self.selected_stop_bits)
+                .flow_control(self.selected_flow_control)
                 .timeout(Duration::from_millis(10))
                 .open()
             {
                 Ok(port) => {
                     self.port_handle = Some(port);
-                    let tx = self.tx.clone();
-                    let mut port = self.port_handle.as_mut().unwrap().try_clone().unwrap();
-                    
-                    thread::spawn(move || {
-                        let mut serial_buf: Vec<u8> = vec![0; 1000];
-                        loop {
-                            if let Ok(t) = port.read(serial_buf.as_mut_slice()) {
-                                if t > 0 {
-                                    if let Ok(s) = String::from_utf8(serial_buf[..t].to_vec()) {
-                                        tx.send(s).unwrap();
+
+                    // Modbus RTU is request/response and polled on demand from
+                    // modbus_poll, so it doesn't use the background reader thread.
+                    if self.protocol_mode != ProtocolMode::ModbusRtu {
+                        let (cmd_tx, cmd_rx) = mpsc::channel();
+                        let tx = self.tx.clone();
+                        let mut port = self.port_handle.as_mut().unwrap().try_clone().unwrap();
+                        let framed_mode = self.protocol_mode == ProtocolMode::FramedPacket;
+                        let message_kind = self.selected_message_kind;
+
+                        let handle = thread::spawn(move || {
+                            let mut serial_buf: Vec<u8> = vec![0; 1000];
+                            let mut cobs_accumulator = CobsAccumulator::new();
+                            loop {
+                                match cmd_rx.try_recv() {
+                                    Ok(PortCmd::Disconnect) | Err(mpsc::TryRecvError::Disconnected) => break,
+                                    Err(mpsc::TryRecvError::Empty) => {}
+                                }
+
+                                if let Ok(t) = port.read(serial_buf.as_mut_slice()) {
+                                    if t > 0 {
+                                        if framed_mode {
+                                            for mut frame in cobs_accumulator.feed(&serial_buf[..t]) {
+                                                if let Some(decoded) = decode_frame(message_kind, &mut frame) {
+                                                    if tx.send(format!("{}\n", decoded).into_bytes()).is_err() {
+                                                        break;
+                                                    }
+                                                }
+                                            }
+                                            if cobs_accumulator.take_overflow() {
+                                                let msg = format!(
+                                                    "[cobs: frame exceeded {} bytes without a delimiter, dropped]\n",
+                                                    MAX_COBS_FRAME_LEN
+                                                );
+                                                if tx.send(msg.into_bytes()).is_err() {
+                                                    break;
+                                                }
+                                            }
+                                        } else if tx.send(serial_buf[..t].to_vec()).is_err() {
+                                            break;
+                                        }
                                     }
                                 }
+                                thread::sleep(Duration::from_millis(10));
                             }
-                            thread::sleep(Duration::from_millis(10));
-                        }
-                    });
+                        });
+
+                        self.cmd_tx = Some(cmd_tx);
+                        self.reader_thread = Some(handle);
+                    }
                 }
                 Err(e) => {
                     println!("Error opening port: {}", e);
@@ -83,6 +452,12 @@ impl ComPortApp {
     }
 
     fn disconnect_port(&mut self) {
+        if let Some(cmd_tx) = self.cmd_tx.take() {
+            let _ = cmd_tx.send(PortCmd::Disconnect);
+        }
+        if let Some(handle) = self.reader_thread.take() {
+            let _ = handle.join();
+        }
         self.port_handle = None;
     }
 
@@ -93,18 +468,163 @@ impl ComPortApp {
             }
         }
     }
+
+    fn record_telemetry_line(&mut self, line: &str) {
+        let fields = parse_telemetry_line(line);
+        if fields.is_empty() {
+            return;
+        }
+
+        let x = match &self.plot_x_field {
+            Some(x_field) => fields
+                .iter()
+                .find(|(name, _)| name == x_field)
+                .map(|(_, value)| *value)
+                .unwrap_or(self.plot_sample_count as f64),
+            None => self.plot_sample_count as f64,
+        };
+
+        for (name, value) in fields {
+            let series = self.plot_series.entry(name).or_default();
+            series.push([x, value]);
+            if series.len() > self.max_plot_points {
+                let overflow = series.len() - self.max_plot_points;
+                series.drain(0..overflow);
+            }
+        }
+
+        self.plot_sample_count += 1;
+    }
+
+    /// Sends one Modbus RTU request for the configured function/address/count
+    /// and parses the reply into `modbus_registers`, or sets `modbus_error`.
+    fn modbus_poll(&mut self) {
+        if !self.modbus_function.is_write()
+            && self.modbus_start_address as u32 + self.modbus_count as u32 > u16::MAX as u32 + 1
+        {
+            self.modbus_error = Some("start address + count exceeds the valid register range".to_string());
+            return;
+        }
+
+        let Some(port) = self.port_handle.as_mut() else {
+            self.modbus_error = Some("not connected".to_string());
+            return;
+        };
+
+        if let Err(e) = port.set_timeout(Duration::from_millis(self.modbus_timeout_ms)) {
+            self.modbus_error = Some(format!("failed to set read timeout: {}", e));
+            return;
+        }
+
+        let mut mreq = ModbusRequest::new(self.modbus_slave_id, ModbusProto::Rtu);
+        let mut request = Vec::new();
+        let write_values: Vec<u16> = self
+            .modbus_write_input
+            .split(',')
+            .filter_map(|v| v.trim().parse().ok())
+            .collect();
+
+        let generated = match self.modbus_function {
+            ModbusFunction::ReadHoldingRegisters => {
+                mreq.generate_get_holdings(self.modbus_start_address, self.modbus_count, &mut request)
+            }
+            ModbusFunction::ReadInputRegisters => {
+                mreq.generate_get_inputs(self.modbus_start_address, self.modbus_count, &mut request)
+            }
+            ModbusFunction::WriteSingleRegister => {
+                let value = write_values.first().copied().unwrap_or(0);
+                mreq.generate_set_holding(self.modbus_start_address, value, &mut request)
+            }
+            ModbusFunction::WriteMultipleRegisters => {
+                mreq.generate_set_holdings_bulk(self.modbus_start_address, &write_values, &mut request)
+            }
+        };
+
+        if let Err(e) = generated {
+            self.modbus_error = Some(format!("{:?}", e));
+            return;
+        }
+
+        if let Err(e) = port.write_all(&request) {
+            self.modbus_error = Some(format!("write error: {}", e));
+            return;
+        }
+
+        let mut header = [0u8; 7];
+        if let Err(e) = port.read_exact(&mut header) {
+            self.modbus_error = Some(format!("read error: {}", e));
+            return;
+        }
+
+        let frame_len = match guess_response_frame_len(&header, ModbusProto::Rtu) {
+            Ok(len) => len as usize,
+            Err(e) => {
+                self.modbus_error = Some(format!("{:?}", e));
+                return;
+            }
+        };
+
+        let mut response = header.to_vec();
+        if frame_len > response.len() {
+            let mut rest = vec![0u8; frame_len - response.len()];
+            if let Err(e) = port.read_exact(&mut rest) {
+                self.modbus_error = Some(format!("read error: {}", e));
+                return;
+            }
+            response.extend_from_slice(&rest);
+        }
+
+        if let Err(e) = mreq.parse_ok(&response) {
+            self.modbus_error = Some(format!("{:?}", e));
+            return;
+        }
+
+        if self.modbus_function.is_write() {
+            self.modbus_registers.clear();
+        } else {
+            let mut values = Vec::new();
+            if let Err(e) = mreq.parse_u16(&response, &mut values) {
+                self.modbus_error = Some(format!("{:?}", e));
+                return;
+            }
+            self.modbus_registers = values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| (self.modbus_start_address.saturating_add(i as u16), value))
+                .collect();
+        }
+
+        self.modbus_error = None;
+    }
 }
 
 impl eframe::App for ComPortApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // 接收port數據
         while let Ok(data) = self.rx.try_recv() {
-            self.output_buffer.push_str(&data);
-            if self.output_buffer.len() > 1000 {
-                self.output_buffer = self.output_buffer.split_off(self.output_buffer.len() - 1000);
+            self.telemetry_line_buffer.push_str(&String::from_utf8_lossy(&data));
+            while let Some(newline_pos) = self.telemetry_line_buffer.find('\n') {
+                let line = self.telemetry_line_buffer[..newline_pos].to_string();
+                self.record_telemetry_line(&line);
+                self.telemetry_line_buffer.drain(..=newline_pos);
+            }
+            if self.telemetry_line_buffer.len() > MAX_TELEMETRY_LINE_BUFFER_BYTES {
+                self.telemetry_line_buffer.clear();
+            }
+
+            self.raw_output.extend_from_slice(&data);
+            if self.raw_output.len() > MAX_RAW_OUTPUT_BYTES {
+                let overflow = self.raw_output.len() - MAX_RAW_OUTPUT_BYTES;
+                self.raw_output.drain(0..overflow);
             }
         }
 
+        self.output_buffer = match self.text_encoding {
+            TextEncoding::Utf8Lossy => String::from_utf8_lossy(&self.raw_output).into_owned(),
+            TextEncoding::LocalCodepage => decode_local_codepage(&self.raw_output),
+            TextEncoding::HexDump => hex_dump(&self.raw_output),
+        };
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
                 if ui.button("refresh ports").clicked() {
@@ -127,6 +647,38 @@ impl eframe::App for ComPortApp {
                         }
                     });
 
+                egui::ComboBox::from_label("data bits")
+                    .selected_text(data_bits_label(self.selected_data_bits))
+                    .show_ui(ui, |ui| {
+                        for &bits in &self.data_bits_options {
+                            ui.selectable_value(&mut self.selected_data_bits, bits, data_bits_label(bits));
+                        }
+                    });
+
+                egui::ComboBox::from_label("parity")
+                    .selected_text(parity_label(self.selected_parity))
+                    .show_ui(ui, |ui| {
+                        for &parity in &self.parity_options {
+                            ui.selectable_value(&mut self.selected_parity, parity, parity_label(parity));
+                        }
+                    });
+
+                egui::ComboBox::from_label("stop bits")
+                    .selected_text(stop_bits_label(self.selected_stop_bits))
+                    .show_ui(ui, |ui| {
+                        for &stop_bits in &self.stop_bits_options {
+                            ui.selectable_value(&mut self.selected_stop_bits, stop_bits, stop_bits_label(stop_bits));
+                        }
+                    });
+
+                egui::ComboBox::from_label("flow control")
+                    .selected_text(flow_control_label(self.selected_flow_control))
+                    .show_ui(ui, |ui| {
+                        for &flow_control in &self.flow_control_options {
+                            ui.selectable_value(&mut self.selected_flow_control, flow_control, flow_control_label(flow_control));
+                        }
+                    });
+
                 if self.port_handle.is_none() {
                     if ui.button("connect").clicked() {
                         self.connect_port();
@@ -138,21 +690,165 @@ impl eframe::App for ComPortApp {
                 }
             });
 
+            // The protocol mode picks which handle (reader thread vs. direct
+            // modbus_poll) reads the port, so it can't change while connected
+            // without two readers racing the same fd. Disconnect first.
+            ui.add_enabled_ui(self.port_handle.is_none(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("mode:");
+                    ui.selectable_value(&mut self.protocol_mode, ProtocolMode::PlainText, "plain text");
+                    ui.selectable_value(&mut self.protocol_mode, ProtocolMode::FramedPacket, "framed packet");
+                    ui.selectable_value(&mut self.protocol_mode, ProtocolMode::ModbusRtu, "modbus RTU");
+
+                    if self.protocol_mode == ProtocolMode::FramedPacket {
+                        egui::ComboBox::from_label("message type")
+                            .selected_text(self.selected_message_kind.label())
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.selected_message_kind,
+                                    MessageKind::SensorReading,
+                                    MessageKind::SensorReading.label(),
+                                );
+                                ui.selectable_value(
+                                    &mut self.selected_message_kind,
+                                    MessageKind::StatusMessage,
+                                    MessageKind::StatusMessage.label(),
+                                );
+                            });
+                    }
+                });
+            });
+
+            if self.protocol_mode == ProtocolMode::ModbusRtu {
+                ui.group(|ui| {
+                    ui.label("modbus RTU master");
+                    ui.horizontal(|ui| {
+                        ui.label("unit id:");
+                        ui.add(egui::DragValue::new(&mut self.modbus_slave_id));
+
+                        egui::ComboBox::from_label("function")
+                            .selected_text(self.modbus_function.label())
+                            .show_ui(ui, |ui| {
+                                for function in [
+                                    ModbusFunction::ReadHoldingRegisters,
+                                    ModbusFunction::ReadInputRegisters,
+                                    ModbusFunction::WriteSingleRegister,
+                                    ModbusFunction::WriteMultipleRegisters,
+                                ] {
+                                    ui.selectable_value(&mut self.modbus_function, function, function.label());
+                                }
+                            });
+
+                        ui.label("start address:");
+                        ui.add(egui::DragValue::new(&mut self.modbus_start_address));
+
+                        ui.label("count:");
+                        ui.add(egui::DragValue::new(&mut self.modbus_count).range(1..=125));
+
+                        ui.label("timeout (ms):");
+                        ui.add(egui::DragValue::new(&mut self.modbus_timeout_ms).range(10..=5000));
+                    });
+
+                    if self.modbus_function.is_write() {
+                        ui.horizontal(|ui| {
+                            ui.label("values (comma-separated):");
+                            ui.text_edit_singleline(&mut self.modbus_write_input);
+                        });
+                    }
+
+                    if ui.button("poll").clicked() {
+                        self.modbus_poll();
+                    }
+
+                    if let Some(error) = &self.modbus_error {
+                        ui.colored_label(egui::Color32::RED, error);
+                    } else if !self.modbus_registers.is_empty() {
+                        egui::Grid::new("modbus_registers").striped(true).show(ui, |ui| {
+                            ui.label("address");
+                            ui.label("value");
+                            ui.end_row();
+                            for (address, value) in &self.modbus_registers {
+                                ui.label(address.to_string());
+                                ui.label(value.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    }
+                });
+            }
+
             ui.separator();
 
             // 輸出顯示區域
             ui.group(|ui| {
-                ui.label("output area");
+                ui.horizontal(|ui| {
+                    ui.label("output area");
+                    egui::ComboBox::from_label("encoding")
+                        .selected_text(self.text_encoding.label())
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.text_encoding, TextEncoding::Utf8Lossy, TextEncoding::Utf8Lossy.label());
+                            ui.selectable_value(&mut self.text_encoding, TextEncoding::LocalCodepage, TextEncoding::LocalCodepage.label());
+                            ui.selectable_value(&mut self.text_encoding, TextEncoding::HexDump, TextEncoding::HexDump.label());
+                        });
+                });
                 ui.add_sized(
                     [ui.available_width(), 200.0],
                     egui::TextEdit::multiline(&mut self.output_buffer)
                         .desired_rows(10)
-                        .lock_focus(true),
+                        .lock_focus(true)
+                        .font(egui::TextStyle::Monospace),
                 );
             });
 
             ui.separator();
 
+            ui.horizontal(|ui| {
+                ui.label("view:");
+                ui.selectable_value(&mut self.view_mode, ViewMode::Text, "text");
+                ui.selectable_value(&mut self.view_mode, ViewMode::Plot, "plot");
+
+                if self.view_mode == ViewMode::Plot {
+                    let known_fields: Vec<String> = self.plot_series.keys().cloned().collect();
+
+                    egui::ComboBox::from_label("x field")
+                        .selected_text(self.plot_x_field.as_deref().unwrap_or("sample #"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.plot_x_field, None, "sample #");
+                            for field in &known_fields {
+                                ui.selectable_value(&mut self.plot_x_field, Some(field.clone()), field);
+                            }
+                        });
+
+                    egui::ComboBox::from_label("y field")
+                        .selected_text(self.plot_y_field.as_deref().unwrap_or(""))
+                        .show_ui(ui, |ui| {
+                            for field in &known_fields {
+                                ui.selectable_value(&mut self.plot_y_field, Some(field.clone()), field);
+                            }
+                        });
+
+                    ui.label("max points:");
+                    ui.add(egui::DragValue::new(&mut self.max_plot_points).range(10..=10000));
+                }
+            });
+
+            if self.view_mode == ViewMode::Plot {
+                ui.group(|ui| {
+                    if let Some(series) = self.plot_y_field.as_ref().and_then(|f| self.plot_series.get(f)) {
+                        let points: PlotPoints = series.clone().into();
+                        Plot::new("telemetry_plot")
+                            .height(200.0)
+                            .show(ui, |plot_ui| {
+                                plot_ui.line(Line::new(points));
+                            });
+                    } else {
+                        ui.label("select a y field to plot");
+                    }
+                });
+
+                ui.separator();
+            }
+
             // 輸入區域
             ui.horizontal(|ui| {
                 let text_edit = ui.add_sized(